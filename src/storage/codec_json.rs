@@ -0,0 +1,64 @@
+#![cfg(feature = "json")]
+
+use super::Codec;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// A codec for storing structured data as JSON, backed by [`serde_json`].
+///
+/// This allows serializing any `T: Serialize + DeserializeOwned`, so `use_local_storage` can be
+/// used with your own structs and enums instead of being limited to primitives.
+///
+/// ## Example
+/// ```
+/// # use leptos::*;
+/// # use serde::{Deserialize, Serialize};
+/// # use leptos_use::storage::{StorageType, use_local_storage, use_session_storage, use_storage, UseStorageOptions, JsonCodec};
+/// #
+/// #[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+/// struct MySettings {
+///     dark_mode: bool,
+/// }
+///
+/// # pub fn Demo() -> impl IntoView {
+/// let (get, set, remove) = use_local_storage::<MySettings, JsonCodec>("settings");
+/// #    view! { }
+/// # }
+/// ```
+#[derive(Clone, Default, PartialEq)]
+pub struct JsonCodec;
+
+impl<T: Serialize + DeserializeOwned> Codec<T> for JsonCodec {
+    type Error = serde_json::Error;
+
+    fn encode(&self, val: &T) -> Result<String, Self::Error> {
+        serde_json::to_string(val)
+    }
+
+    fn decode(&self, str: String) -> Result<T, Self::Error> {
+        serde_json::from_str(&str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct Example {
+        name: String,
+        count: i32,
+    }
+
+    #[test]
+    fn test_json_codec() {
+        let example = Example {
+            name: String::from("party time 🎉"),
+            count: 42,
+        };
+        let codec = JsonCodec;
+        let encoded = codec.encode(&example).unwrap();
+        assert_eq!(codec.decode(encoded).ok(), Some(example));
+    }
+}
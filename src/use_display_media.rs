@@ -1,7 +1,7 @@
 use leptos::*;
 use wasm_bindgen::{JsValue, JsCast};
 use web_sys::{DisplayMediaStreamConstraints, MediaStream};
-use crate::use_window::use_window;
+use crate::use_media::media_devices;
 
 
 /// Get a Resource containing a media stream from the user's display.
@@ -42,10 +42,7 @@ where
 }
 
 async fn create_media(opts: Option<DisplayMediaStreamConstraints>) -> Result<MediaStream, JsValue> {
-    let media = use_window()
-        .navigator()
-        .ok_or_else(|| JsValue::from_str("Failed to access window.navigator"))
-        .and_then(|n| n.media_devices())?;
+    let media = media_devices()?;
 
     let promise = match opts {
         Some(o) => media.get_display_media_with_constraints(&o),
@@ -0,0 +1,14 @@
+use wasm_bindgen::JsValue;
+use web_sys::MediaDevices;
+
+use crate::use_window::use_window;
+
+/// Shared helper for accessing `navigator.mediaDevices`, used by both
+/// [`use_display_media`](crate::use_display_media) and
+/// [`use_user_media`](crate::use_user_media) to acquire and enumerate media streams.
+pub(crate) fn media_devices() -> Result<MediaDevices, JsValue> {
+    use_window()
+        .navigator()
+        .ok_or_else(|| JsValue::from_str("Failed to access window.navigator"))
+        .and_then(|n| n.media_devices())
+}
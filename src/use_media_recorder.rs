@@ -0,0 +1,212 @@
+use leptos::*;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Blob, BlobEvent, MediaRecorder, MediaRecorderOptions, MediaStream, Url};
+
+/// The current state of a [`use_media_recorder`] recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingState {
+    Inactive,
+    Recording,
+    Paused,
+}
+
+/// Wraps [`MediaRecorder`] to record a `MediaStream` into timed chunks.
+///
+/// When `timeslice_ms` is `Some`, the recorder fires `dataavailable` on that interval so
+/// consumers can upload segments incrementally, rather than only getting one `Blob` at `stop()`.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_display_media, use_media_recorder::{use_media_recorder, UseMediaRecorderReturn}};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let stream = use_display_media(None);
+///
+/// let UseMediaRecorderReturn { start, stop, object_url, .. } = use_media_recorder(
+///     Signal::derive(move || stream.get().and_then(Result::ok)),
+///     "video/webm",
+///     Some(1000),
+/// );
+/// #
+/// #   view! { }
+/// # }
+/// ```
+pub fn use_media_recorder(
+    stream: Signal<Option<MediaStream>>,
+    mime_type: &str,
+    timeslice_ms: Option<i32>,
+) -> UseMediaRecorderReturn<
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+> {
+    let mime_type = mime_type.to_string();
+    let recorder = store_value(None::<MediaRecorder>);
+
+    let (recording_state, set_recording_state) = create_signal(RecordingState::Inactive);
+    let (chunk, set_chunk) = create_signal(None::<Blob>);
+    let (blobs, set_blobs) = create_signal(Vec::<Blob>::new());
+    let (object_url, set_object_url) = create_signal(None::<String>);
+
+    create_effect(move |_| {
+        let Some(stream) = stream.get() else {
+            recorder.set_value(None);
+            return;
+        };
+
+        let mut options = MediaRecorderOptions::new();
+        options.mime_type(&mime_type);
+        let Ok(new_recorder) = MediaRecorder::new_with_media_stream_and_media_recorder_options(
+            &stream, &options,
+        ) else {
+            logging::error!("use_media_recorder: failed to construct MediaRecorder");
+            return;
+        };
+
+        let ondataavailable = Closure::<dyn FnMut(BlobEvent)>::new(move |ev: BlobEvent| {
+            let Some(blob) = ev.data() else { return };
+            set_chunk.set(Some(blob.clone()));
+            set_blobs.update(|blobs| blobs.push(blob));
+
+            let blobs = blobs.get_untracked();
+            let sequence = js_sys::Array::new();
+            for blob in &blobs {
+                sequence.push(blob);
+            }
+            if let Ok(blob) = Blob::new_with_blob_sequence(&sequence) {
+                if let Some(old_url) = object_url.get_untracked() {
+                    _ = Url::revoke_object_url(&old_url);
+                }
+                if let Ok(url) = Url::create_object_url_with_blob(&blob) {
+                    set_object_url.set(Some(url));
+                }
+            }
+        });
+        new_recorder.set_ondataavailable(Some(ondataavailable.as_ref().unchecked_ref()));
+        ondataavailable.forget();
+
+        let onstart = Closure::<dyn FnMut()>::new(move || {
+            set_recording_state.set(RecordingState::Recording);
+        });
+        new_recorder.set_onstart(Some(onstart.as_ref().unchecked_ref()));
+        onstart.forget();
+
+        let onpause = Closure::<dyn FnMut()>::new(move || {
+            set_recording_state.set(RecordingState::Paused);
+        });
+        new_recorder.set_onpause(Some(onpause.as_ref().unchecked_ref()));
+        onpause.forget();
+
+        let onresume = Closure::<dyn FnMut()>::new(move || {
+            set_recording_state.set(RecordingState::Recording);
+        });
+        new_recorder.set_onresume(Some(onresume.as_ref().unchecked_ref()));
+        onresume.forget();
+
+        let onstop = Closure::<dyn FnMut()>::new(move || {
+            set_recording_state.set(RecordingState::Inactive);
+        });
+        new_recorder.set_onstop(Some(onstop.as_ref().unchecked_ref()));
+        onstop.forget();
+
+        recorder.set_value(Some(new_recorder.clone()));
+
+        // Runs before this effect's next execution (e.g. the stream changing or the owning
+        // scope being disposed), so a still-recording `MediaRecorder` never outlives its stream.
+        on_cleanup(move || {
+            if !matches!(new_recorder.state(), web_sys::RecordingState::Inactive) {
+                _ = new_recorder.stop();
+            }
+            if let Some(old_url) = object_url.get_untracked() {
+                _ = Url::revoke_object_url(&old_url);
+                set_object_url.set(None);
+            }
+        });
+    });
+
+    let start = move || {
+        recorder.with_value(|recorder| {
+            let Some(recorder) = recorder else { return };
+            let result = match timeslice_ms {
+                Some(ms) => recorder.start_with_time_slice(ms),
+                None => recorder.start(),
+            };
+            if let Err(e) = result {
+                logging::error!("use_media_recorder: failed to start recording: {:?}", e);
+            }
+        });
+    };
+
+    let stop = move || {
+        recorder.with_value(|recorder| {
+            if let Some(recorder) = recorder {
+                _ = recorder.stop();
+            }
+        });
+    };
+
+    let pause = move || {
+        recorder.with_value(|recorder| {
+            if let Some(recorder) = recorder {
+                _ = recorder.pause();
+            }
+        });
+    };
+
+    let resume = move || {
+        recorder.with_value(|recorder| {
+            if let Some(recorder) = recorder {
+                _ = recorder.resume();
+            }
+        });
+    };
+
+    UseMediaRecorderReturn {
+        recording_state: recording_state.into(),
+        chunk: chunk.into(),
+        blobs: blobs.into(),
+        object_url: object_url.into(),
+        start,
+        stop,
+        pause,
+        resume,
+    }
+}
+
+/// Return type of [`use_media_recorder`].
+pub struct UseMediaRecorderReturn<Start, Stop, Pause, Resume>
+where
+    Start: Fn(),
+    Stop: Fn(),
+    Pause: Fn(),
+    Resume: Fn(),
+{
+    /// The current recording state.
+    pub recording_state: Signal<RecordingState>,
+
+    /// The most recent chunk delivered by a `dataavailable` event.
+    pub chunk: Signal<Option<Blob>>,
+
+    /// Every chunk delivered so far, in order.
+    pub blobs: Signal<Vec<Blob>>,
+
+    /// An object URL for the chunks accumulated so far, suitable for playback or download.
+    pub object_url: Signal<Option<String>>,
+
+    /// Starts (or resumes a freshly constructed) recording.
+    pub start: Start,
+
+    /// Stops the recording.
+    pub stop: Stop,
+
+    /// Pauses the recording.
+    pub pause: Pause,
+
+    /// Resumes a paused recording.
+    pub resume: Resume,
+}
@@ -0,0 +1,67 @@
+use leptos::*;
+use serde_json::{json, Value};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::RtcPeerConnection;
+
+use crate::use_window::use_window;
+use crate::utils::js_value_to_json;
+
+/// Polls [`RtcPeerConnection::get_stats`] every `interval_ms` milliseconds and exposes the
+/// resulting `RtcStatsReport` as a reactive [`serde_json::Value`].
+///
+/// An `RtcStatsReport` is a JS `Map` of stat id to stat dictionary, so it's converted directly
+/// by [`js_value_to_json`], making it trivially loggable or renderable.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_rtc_stats::use_rtc_stats;
+/// # use web_sys::RtcPeerConnection;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let pc = RtcPeerConnection::new().expect("failed to create RtcPeerConnection");
+/// let stats = use_rtc_stats(pc, 1000);
+///
+/// # view! { <pre>{move || stats.get().to_string()}</pre> }
+/// # }
+/// ```
+pub fn use_rtc_stats(pc: RtcPeerConnection, interval_ms: i32) -> Signal<Value> {
+    let (stats, set_stats) = create_signal(json!({}));
+
+    let poll = move || {
+        let pc = pc.clone();
+        spawn_local(async move {
+            match JsFuture::from(pc.get_stats()).await {
+                Ok(report) => {
+                    if let Some(value) = js_value_to_json(&report) {
+                        set_stats.set(value);
+                    }
+                }
+                Err(e) => logging::error!("use_rtc_stats: get_stats failed: {:?}", e),
+            }
+        });
+    };
+
+    poll();
+
+    let closure = Closure::<dyn FnMut()>::new(poll);
+    let interval_id = use_window()
+        .set_interval_with_callback_and_timeout_and_arguments_0(
+            closure.as_ref().unchecked_ref(),
+            interval_ms,
+        )
+        .ok();
+    closure.forget();
+
+    on_cleanup(move || {
+        if let Some(id) = interval_id {
+            use_window().clear_interval_with_handle(id);
+        }
+    });
+
+    stats.into()
+}
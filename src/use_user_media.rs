@@ -0,0 +1,204 @@
+use js_sys::{Object, Reflect};
+use leptos::*;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    MediaDeviceInfo, MediaDeviceKind, MediaStream, MediaStreamConstraints, MediaStreamTrack,
+    MediaTrackConstraints,
+};
+
+use crate::use_media::media_devices;
+
+/// Get a Resource containing a media stream from the user's camera and/or microphone, along
+/// with controls to toggle tracks and switch between devices.
+///
+/// ## Demo
+///
+/// [Link to Demo](https://github.com/Synphonyte/leptos-use/tree/main/examples/use_user_media)
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_user_media, UseUserMediaReturn};
+/// # use web_sys::MediaStreamConstraints;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// #   let mut constraints = MediaStreamConstraints::new();
+/// #   constraints.video(&wasm_bindgen::JsValue::TRUE);
+/// #   constraints.audio(&wasm_bindgen::JsValue::TRUE);
+/// #   let UseUserMediaReturn { stream, enable_video, enable_audio, devices, switch_device } =
+/// #       use_user_media(constraints);
+/// #
+/// #  let video_ref = create_node_ref::<leptos::html::Video>();
+/// #    create_effect(move |_| match stream.get() {
+/// #        Some(Ok(s)) => {
+/// #            video_ref.get().expect("video element ref not created").set_src_object(Some(&s));
+/// #            video_ref.get().map(|v| v.play());
+/// #        }
+/// #        Some(Err(e)) => log::error!("Failed to get media stream: {:?}", e),
+/// #        None => log::debug!("No stream yet"),
+/// #    });
+/// #
+/// #    view! { <video _ref=video_ref controls=true autoplay=true muted=true></video> }
+/// # }
+/// ```
+pub fn use_user_media<C>(
+    constraints: C,
+) -> UseUserMediaReturn<impl Fn(bool) + Clone, impl Fn(bool) + Clone, impl Fn(String) + Clone>
+where
+    C: Into<MaybeSignal<MediaStreamConstraints>>,
+{
+    let constraints: MaybeSignal<MediaStreamConstraints> = constraints.into();
+    let (device_override, set_device_override) = create_signal(None::<(MediaDeviceKind, String)>);
+
+    let stream = create_local_resource(
+        move || (constraints.get(), device_override.get()),
+        |(constraints, device_override)| async move {
+            let constraints = match device_override {
+                Some((kind, device_id)) => with_device_id(&constraints, kind, &device_id),
+                None => constraints,
+            };
+            create_user_media(constraints).await
+        },
+    );
+
+    let (devices, set_devices) = create_signal(Vec::<MediaDeviceInfo>::new());
+
+    create_effect(move |_| {
+        spawn_local(async move {
+            match enumerate_devices().await {
+                Ok(devs) => set_devices.set(devs),
+                Err(e) => logging::error!("use_user_media: failed to enumerate devices: {:?}", e),
+            }
+        });
+    });
+
+    let enable_video =
+        move |enabled: bool| set_track_enabled(&stream, MediaStream::get_video_tracks, enabled);
+    let enable_audio =
+        move |enabled: bool| set_track_enabled(&stream, MediaStream::get_audio_tracks, enabled);
+    let switch_device = move |device_id: String| {
+        let kind = devices
+            .get_untracked()
+            .iter()
+            .find(|device| device.device_id() == device_id)
+            .map(MediaDeviceInfo::kind);
+
+        let Some(kind) = kind else {
+            logging::error!(
+                "use_user_media: switch_device called with unknown device id {device_id:?}"
+            );
+            return;
+        };
+
+        set_device_override.set(Some((kind, device_id)));
+    };
+
+    UseUserMediaReturn {
+        stream,
+        enable_video,
+        enable_audio,
+        devices: devices.into(),
+        switch_device,
+    }
+}
+
+async fn create_user_media(constraints: MediaStreamConstraints) -> Result<MediaStream, JsValue> {
+    let media = media_devices()?;
+    let promise = media.get_user_media_with_constraints(&constraints)?;
+    let res = JsFuture::from(promise).await?;
+    Ok::<_, JsValue>(MediaStream::unchecked_from_js(res))
+}
+
+async fn enumerate_devices() -> Result<Vec<MediaDeviceInfo>, JsValue> {
+    let media = media_devices()?;
+    let promise = media.enumerate_devices()?;
+    let res = JsFuture::from(promise).await?;
+    let devices = js_sys::Array::from(&res)
+        .iter()
+        .map(MediaDeviceInfo::unchecked_from_js)
+        .collect();
+    Ok(devices)
+}
+
+/// Returns a copy of `constraints` with the `deviceId` of the constraint matching `kind` (video
+/// or audio) set to `device_id`, preserving any other constraints already set for that kind.
+fn with_device_id(
+    constraints: &MediaStreamConstraints,
+    kind: MediaDeviceKind,
+    device_id: &str,
+) -> MediaStreamConstraints {
+    let constraints =
+        Object::assign(&Object::new(), constraints).unchecked_into::<MediaStreamConstraints>();
+
+    let key = match kind {
+        MediaDeviceKind::Videoinput => "video",
+        MediaDeviceKind::Audioinput => "audio",
+        _ => return constraints,
+    };
+
+    let existing =
+        Reflect::get(&constraints, &JsValue::from_str(key)).unwrap_or(JsValue::UNDEFINED);
+
+    let merged = MediaTrackConstraints::new();
+    if existing.is_object() {
+        _ = Object::assign(merged.unchecked_ref(), existing.unchecked_ref());
+    }
+    merged.device_id(&JsValue::from_str(device_id));
+
+    match kind {
+        MediaDeviceKind::Videoinput => constraints.video(&merged),
+        MediaDeviceKind::Audioinput => constraints.audio(&merged),
+        _ => unreachable!(),
+    };
+
+    constraints
+}
+
+fn set_track_enabled(
+    stream: &Resource<
+        (MediaStreamConstraints, Option<(MediaDeviceKind, String)>),
+        Result<MediaStream, JsValue>,
+    >,
+    get_tracks: impl Fn(&MediaStream) -> js_sys::Array,
+    enabled: bool,
+) {
+    let Some(Ok(stream)) = stream.get() else {
+        return;
+    };
+
+    for track in get_tracks(&stream).iter() {
+        track
+            .unchecked_into::<MediaStreamTrack>()
+            .set_enabled(enabled);
+    }
+}
+
+/// Return type of [`use_user_media`].
+pub struct UseUserMediaReturn<EnableVideo, EnableAudio, SwitchDevice>
+where
+    EnableVideo: Fn(bool),
+    EnableAudio: Fn(bool),
+    SwitchDevice: Fn(String),
+{
+    /// The underlying camera/microphone `MediaStream`, as acquired from `getUserMedia`.
+    pub stream: Resource<
+        (MediaStreamConstraints, Option<(MediaDeviceKind, String)>),
+        Result<MediaStream, JsValue>,
+    >,
+
+    /// Toggles the `enabled` flag on every video track without tearing down the stream.
+    pub enable_video: EnableVideo,
+
+    /// Toggles the `enabled` flag on every audio track without tearing down the stream.
+    pub enable_audio: EnableAudio,
+
+    /// The devices available via `enumerateDevices`, refreshed whenever the hook is created.
+    pub devices: Signal<Vec<MediaDeviceInfo>>,
+
+    /// Re-acquires the stream with the `deviceId` of the matching kind (video or audio) updated
+    /// to the given device, looked up from [`devices`](UseUserMediaReturn::devices) by id.
+    pub switch_device: SwitchDevice,
+}
@@ -0,0 +1,270 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{
+    MediaStream, MediaStreamTrack, RtcIceCandidate, RtcIceCandidateInit, RtcPeerConnection,
+    RtcPeerConnectionIceEvent, RtcPeerConnectionState, RtcRtpSender, RtcSdpType,
+    RtcSessionDescription, RtcSessionDescriptionInit, RtcSignalingState, RtcTrackEvent,
+};
+
+/// A message exchanged between the two ends of a [`use_webrtc`] connection while negotiating.
+///
+/// A [`SignalMessage::IceCandidate`] with an empty `candidate` marks the end of ICE gathering
+/// for the peer that sent it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SignalMessage {
+    Offer(String),
+    Answer(String),
+    IceCandidate {
+        candidate: String,
+        sdp_mid: Option<String>,
+        sdp_m_line_index: Option<u16>,
+    },
+}
+
+/// A transport used by [`use_webrtc`] to carry [`SignalMessage`]s to and from the remote peer.
+///
+/// Implementations are free to use whatever channel is convenient (a `WebSocket`, a REST polling
+/// endpoint, ...) as long as messages arrive in order.
+pub trait WebRtcSignaller {
+    /// Send a message to the remote peer.
+    fn send(&self, msg: SignalMessage);
+
+    /// The most recently received message from the remote peer.
+    fn messages(&self) -> Signal<Option<SignalMessage>>;
+}
+
+/// Negotiates and drives a [`RtcPeerConnection`] between two peers.
+///
+/// `stream` is the local media to publish (for example the `MediaStream` returned by
+/// [`use_display_media`](crate::use_display_media) or
+/// [`use_user_media`](crate::use_user_media)); it may start out `None` and be set later. Set
+/// `is_offerer` to `true` on exactly one side of the connection: that side creates the initial
+/// SDP offer once a local stream is available, while the other side waits for it and answers.
+///
+/// ICE candidates are trickled to the signaller as soon as they're discovered rather than
+/// batched until gathering completes, with a candidate of `""` signalling end-of-gathering.
+///
+/// Fails if the browser refuses to construct an `RtcPeerConnection` at all (privacy extensions
+/// and some enterprise policies neuter `window.RTCPeerConnection`).
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_webrtc::{use_webrtc, SignalMessage, UseWebRtcReturn, WebRtcSignaller};
+/// # use web_sys::MediaStream;
+/// #
+/// # #[derive(Clone)]
+/// # struct MySignaller;
+/// # impl WebRtcSignaller for MySignaller {
+/// #     fn send(&self, _msg: SignalMessage) {}
+/// #     fn messages(&self) -> Signal<Option<SignalMessage>> { create_signal(None).0.into() }
+/// # }
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (stream, _set_stream) = create_signal(None::<MediaStream>);
+///
+/// let UseWebRtcReturn { remote_stream, connection_state, .. } =
+///     use_webrtc(stream.into(), MySignaller, true).expect("failed to create RtcPeerConnection");
+/// #
+/// #   view! { }
+/// # }
+/// ```
+pub fn use_webrtc<Sig>(
+    stream: Signal<Option<MediaStream>>,
+    signaller: Sig,
+    is_offerer: bool,
+) -> Result<UseWebRtcReturn<impl Fn() + Clone>, JsValue>
+where
+    Sig: WebRtcSignaller + Clone + 'static,
+{
+    let pc = RtcPeerConnection::new()?;
+
+    let (remote_stream, set_remote_stream) = create_signal(None::<MediaStream>);
+    let (connection_state, set_connection_state) = create_signal(RtcPeerConnectionState::New);
+
+    let ontrack = Closure::<dyn FnMut(RtcTrackEvent)>::new(move |ev: RtcTrackEvent| {
+        if let Some(stream) = ev.streams().get(0).dyn_ref::<MediaStream>() {
+            set_remote_stream.set(Some(stream.clone()));
+        }
+    });
+    pc.set_ontrack(Some(ontrack.as_ref().unchecked_ref()));
+    ontrack.forget();
+
+    let pc_for_state = pc.clone();
+    let onconnectionstatechange = Closure::<dyn FnMut()>::new(move || {
+        set_connection_state.set(pc_for_state.connection_state());
+    });
+    pc.set_onconnectionstatechange(Some(onconnectionstatechange.as_ref().unchecked_ref()));
+    onconnectionstatechange.forget();
+
+    let signaller_for_ice = signaller.clone();
+    let onicecandidate = Closure::<dyn FnMut(RtcPeerConnectionIceEvent)>::new(
+        move |ev: RtcPeerConnectionIceEvent| {
+            let msg = match ev.candidate() {
+                Some(candidate) => SignalMessage::IceCandidate {
+                    candidate: candidate.candidate(),
+                    sdp_mid: candidate.sdp_mid(),
+                    sdp_m_line_index: candidate.sdp_m_line_index(),
+                },
+                None => SignalMessage::IceCandidate {
+                    candidate: String::new(),
+                    sdp_mid: None,
+                    sdp_m_line_index: None,
+                },
+            };
+            signaller_for_ice.send(msg);
+        },
+    );
+    pc.set_onicecandidate(Some(onicecandidate.as_ref().unchecked_ref()));
+    onicecandidate.forget();
+
+    let pc_for_tracks = pc.clone();
+    let signaller_for_offer = signaller.clone();
+    let senders = store_value(Vec::<RtcRtpSender>::new());
+    create_effect(move |_| {
+        // Drop whatever we sent from a previous `local_stream` (e.g. after a device switch)
+        // before adding tracks for the new one, so senders don't accumulate on the connection.
+        senders.update_value(|senders| {
+            for sender in senders.drain(..) {
+                _ = pc_for_tracks.remove_track(&sender);
+            }
+        });
+
+        let Some(local_stream) = stream.get() else {
+            return;
+        };
+
+        let new_senders = local_stream
+            .get_tracks()
+            .iter()
+            .filter_map(|track| {
+                let track = track.unchecked_into::<MediaStreamTrack>();
+                pc_for_tracks.add_track(&track, &local_stream).ok()
+            })
+            .collect();
+        senders.set_value(new_senders);
+
+        // Only (re)negotiate from a stable connection: if a prior offer/answer exchange is still
+        // in flight (e.g. `switch_device` swapped the stream before the remote answer arrived),
+        // `create_offer`/`set_local_description` would reject instead of renegotiating.
+        if is_offerer && pc_for_tracks.signaling_state() == RtcSignalingState::Stable {
+            let pc = pc_for_tracks.clone();
+            let signaller = signaller_for_offer.clone();
+            spawn_local(async move {
+                if let Err(e) = create_and_send_offer(&pc, &signaller).await {
+                    logging::error!("use_webrtc: failed to create offer: {:?}", e);
+                }
+            });
+        }
+    });
+
+    let pc_for_messages = pc.clone();
+    let signaller_for_messages = signaller.clone();
+    create_effect(move |_| {
+        let Some(msg) = signaller_for_messages.messages().get() else {
+            return;
+        };
+
+        let pc = pc_for_messages.clone();
+        let signaller = signaller_for_messages.clone();
+        spawn_local(async move {
+            if let Err(e) = handle_signal_message(&pc, &signaller, msg).await {
+                logging::error!("use_webrtc: failed to handle signal message: {:?}", e);
+            }
+        });
+    });
+
+    let pc_for_close = pc.clone();
+    let close = move || pc_for_close.close();
+
+    Ok(UseWebRtcReturn {
+        remote_stream: remote_stream.into(),
+        connection_state: connection_state.into(),
+        close,
+    })
+}
+
+async fn create_and_send_offer<Sig: WebRtcSignaller>(
+    pc: &RtcPeerConnection,
+    signaller: &Sig,
+) -> Result<(), JsValue> {
+    let offer = JsFuture::from(pc.create_offer())
+        .await?
+        .unchecked_into::<RtcSessionDescription>();
+
+    let mut desc = RtcSessionDescriptionInit::new(RtcSdpType::Offer);
+    desc.sdp(&offer.sdp());
+    JsFuture::from(pc.set_local_description(&desc)).await?;
+
+    signaller.send(SignalMessage::Offer(offer.sdp()));
+    Ok(())
+}
+
+async fn handle_signal_message<Sig: WebRtcSignaller>(
+    pc: &RtcPeerConnection,
+    signaller: &Sig,
+    msg: SignalMessage,
+) -> Result<(), JsValue> {
+    match msg {
+        SignalMessage::Offer(sdp) => {
+            let mut desc = RtcSessionDescriptionInit::new(RtcSdpType::Offer);
+            desc.sdp(&sdp);
+            JsFuture::from(pc.set_remote_description(&desc)).await?;
+
+            let answer = JsFuture::from(pc.create_answer())
+                .await?
+                .unchecked_into::<RtcSessionDescription>();
+
+            let mut answer_desc = RtcSessionDescriptionInit::new(RtcSdpType::Answer);
+            answer_desc.sdp(&answer.sdp());
+            JsFuture::from(pc.set_local_description(&answer_desc)).await?;
+
+            signaller.send(SignalMessage::Answer(answer.sdp()));
+        }
+        SignalMessage::Answer(sdp) => {
+            let mut desc = RtcSessionDescriptionInit::new(RtcSdpType::Answer);
+            desc.sdp(&sdp);
+            JsFuture::from(pc.set_remote_description(&desc)).await?;
+        }
+        SignalMessage::IceCandidate {
+            candidate,
+            sdp_mid,
+            sdp_m_line_index,
+        } => {
+            // An empty candidate is the end-of-gathering marker; there's nothing to add.
+            if candidate.is_empty() {
+                return Ok(());
+            }
+
+            let mut init = RtcIceCandidateInit::new(&candidate);
+            init.sdp_mid(sdp_mid.as_deref());
+            init.sdp_m_line_index(sdp_m_line_index);
+            let candidate = RtcIceCandidate::new(&init)?;
+
+            JsFuture::from(pc.add_ice_candidate_with_opt_rtc_ice_candidate(Some(&candidate)))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Return type of [`use_webrtc`].
+pub struct UseWebRtcReturn<F>
+where
+    F: Fn() + Clone,
+{
+    /// The `MediaStream` received from the remote peer, once `ontrack` has fired.
+    pub remote_stream: Signal<Option<MediaStream>>,
+
+    /// The current state of the underlying `RtcPeerConnection`.
+    pub connection_state: Signal<RtcPeerConnectionState>,
+
+    /// Closes the peer connection.
+    pub close: F,
+}
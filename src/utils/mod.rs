@@ -0,0 +1,151 @@
+use js_sys::{Array, Function, Iterator as JsIterator, Object};
+use serde_json::{Map as JsonMap, Number, Value};
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Recursively converts a dynamic `JsValue` into a [`serde_json::Value`].
+///
+/// Numbers, booleans and strings map to their JSON scalar equivalents. Arrays map to JSON
+/// arrays. JS `Map`s and "maplike" objects (anything exposing a callable `entries()`, which
+/// includes WebIDL maplike interfaces such as `RtcStatsReport` even though they don't inherit
+/// from `Map.prototype`) map to JSON objects by walking their entries. Plain objects map to JSON
+/// objects via `Object.entries`. Anything that doesn't fit one of these shapes (functions,
+/// symbols, ...) yields `None`.
+pub fn js_value_to_json(value: &JsValue) -> Option<Value> {
+    if value.is_null() || value.is_undefined() {
+        return Some(Value::Null);
+    }
+
+    if let Some(b) = value.as_bool() {
+        return Some(Value::Bool(b));
+    }
+
+    if let Some(n) = value.as_f64() {
+        return Number::from_f64(n).map(Value::Number);
+    }
+
+    if let Some(s) = value.as_string() {
+        return Some(Value::String(s));
+    }
+
+    if Array::is_array(value) {
+        let array = Array::from(value);
+        let mut values = Vec::with_capacity(array.length() as usize);
+        for item in array.iter() {
+            values.push(js_value_to_json(&item)?);
+        }
+        return Some(Value::Array(values));
+    }
+
+    if let Some(entries) = entries_of(value) {
+        let mut object = JsonMap::new();
+        for entry in entries {
+            let entry = Array::from(&entry.ok()?);
+            let key = entry.get(0).as_string()?;
+            let value = js_value_to_json(&entry.get(1))?;
+            object.insert(key, value);
+        }
+        return Some(Value::Object(object));
+    }
+
+    if value.is_object() {
+        let mut object = JsonMap::new();
+        for entry in Object::entries(value.unchecked_ref()).iter() {
+            let entry = Array::from(&entry);
+            let key = entry.get(0).as_string()?;
+            let value = js_value_to_json(&entry.get(1))?;
+            object.insert(key, value);
+        }
+        return Some(Value::Object(object));
+    }
+
+    None
+}
+
+/// Calls `value.entries()` and returns the resulting iterator, if `value` has a callable
+/// `entries` method. This covers both real `js_sys::Map`s and WebIDL "maplike" objects (such as
+/// `RtcStatsReport`) that expose the same `entries`/`forEach` surface without actually inheriting
+/// from `Map.prototype`, so `instanceof Map` would miss them.
+fn entries_of(value: &JsValue) -> Option<JsIterator> {
+    let entries = js_sys::Reflect::get(value, &JsValue::from_str("entries")).ok()?;
+    let entries = entries.dyn_ref::<Function>()?;
+    entries.call0(value).ok()?.dyn_into::<JsIterator>().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use js_sys::Map;
+    use wasm_bindgen::closure::Closure;
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(js_value_to_json(&JsValue::NULL), Some(Value::Null));
+        assert_eq!(js_value_to_json(&JsValue::TRUE), Some(Value::Bool(true)));
+        assert_eq!(
+            js_value_to_json(&JsValue::from_f64(4.2)),
+            Some(Value::Number(Number::from_f64(4.2).unwrap()))
+        );
+        assert_eq!(
+            js_value_to_json(&JsValue::from_str("hi")),
+            Some(Value::String("hi".into()))
+        );
+    }
+
+    #[test]
+    fn test_array() {
+        let array = Array::new();
+        array.push(&JsValue::from_f64(1.0));
+        array.push(&JsValue::from_f64(2.0));
+        assert_eq!(
+            js_value_to_json(&array),
+            Some(Value::Array(vec![
+                Value::Number(Number::from_f64(1.0).unwrap()),
+                Value::Number(Number::from_f64(2.0).unwrap()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        let map = Map::new();
+        map.set(&JsValue::from_str("a"), &JsValue::from_f64(1.0));
+
+        let mut expected = JsonMap::new();
+        expected.insert("a".into(), Value::Number(Number::from_f64(1.0).unwrap()));
+        assert_eq!(js_value_to_json(&map), Some(Value::Object(expected)));
+    }
+
+    #[test]
+    fn test_maplike_object() {
+        // A WebIDL maplike object (e.g. `RtcStatsReport`) exposes `entries()`/`forEach()` like a
+        // `Map`, but doesn't inherit from `Map.prototype`, so `instanceof Map` is false for it.
+        // Simulate that shape with a plain object carrying its own `entries` method.
+        let backing_map = Map::new();
+        backing_map.set(&JsValue::from_str("b"), &JsValue::from_f64(2.0));
+
+        let maplike = Object::new();
+        let entries =
+            Closure::wrap(Box::new(move || backing_map.entries()) as Box<dyn FnMut() -> JsIterator>);
+        js_sys::Reflect::set(
+            &maplike,
+            &JsValue::from_str("entries"),
+            entries.as_ref().unchecked_ref(),
+        )
+        .unwrap();
+        entries.forget();
+
+        let mut expected = JsonMap::new();
+        expected.insert("b".into(), Value::Number(Number::from_f64(2.0).unwrap()));
+        assert_eq!(js_value_to_json(&maplike), Some(Value::Object(expected)));
+    }
+
+    #[test]
+    fn test_plain_object() {
+        let object = Object::new();
+        js_sys::Reflect::set(&object, &JsValue::from_str("c"), &JsValue::from_f64(3.0)).unwrap();
+
+        let mut expected = JsonMap::new();
+        expected.insert("c".into(), Value::Number(Number::from_f64(3.0).unwrap()));
+        assert_eq!(js_value_to_json(&object), Some(Value::Object(expected)));
+    }
+}